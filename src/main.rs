@@ -1,7 +1,9 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use std::f64::consts::PI;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use toml;
 use bevy::{
     input::mouse::MouseWheel,
@@ -14,11 +16,26 @@ const DEFAULT_WINDOW_HEIGHT: f32 = 900.0;
 const TILE_SIZE: f32 = 50.0;
 const TILE_RANGE: i32 = 10;
 
+// Serde schema for a `.toml` tile map: a list of tile type keys, a 2D array
+// of indices into that list (one inner vec per grid column), and the things
+// (pawns) standing on the grid.
+#[derive(Serialize, Deserialize)]
 struct MapInfo {
     tile_keys: Vec<String>,
     tile_values: Vec<Vec<u8>>,
+    things: Vec<ThingRecord>,
 }
 
+// One placed Thing: its tile and whether it is a controllable pawn.
+#[derive(Serialize, Deserialize)]
+struct ThingRecord {
+    position: [i32; 2],
+    pawn: bool,
+}
+
+const MAP_PATH: &str = "map.toml";
+const DEFAULT_TILE_KEY: &str = "debug_tile";
+
 enum TileKey {
     Decorationable(),
 }
@@ -26,12 +43,14 @@ enum TileKey {
 #[derive(Component)]
 struct Tile {
     position: IVec2,
+    key: String,
 }
 
 #[derive(PartialEq)]
 enum PawnState {
     Idle,
-    Move(Vec2),
+    /// Remaining tile waypoints to walk through, front-to-back.
+    Move(VecDeque<IVec2>),
 }
 
 #[derive(Component)]
@@ -56,10 +75,36 @@ struct Thing {
 #[derive(Component)]
 struct Selecter;
 
+// Tracks the anchor of an in-progress rubber-band selection.
+#[derive(Resource, Default)]
+struct DragSelect {
+    anchor: Option<Vec2>,
+}
+
+#[derive(Component)]
+struct SelectionBox;
+
 #[derive(Resource)]
 struct Settings {
     camera_move_speed: f32,
     camera_zoom_speed: f32,
+    separation_weight: f32,
+    separation_radius: f32,
+    // Camera feel.
+    edge_scroll_margin: f32,
+    pan_acceleration: f32,
+    pan_damping: f32,
+    zoom_smoothing: f32,
+    focus_speed: f32,
+    min_scale: f32,
+    max_scale: f32,
+    // Rebindable key actions.
+    key_up: Vec<KeyCode>,
+    key_down: Vec<KeyCode>,
+    key_left: Vec<KeyCode>,
+    key_right: Vec<KeyCode>,
+    key_fast: Vec<KeyCode>,
+    key_focus: Vec<KeyCode>,
 }
 
 impl Default for Settings {
@@ -67,6 +112,45 @@ impl Default for Settings {
         Self {
             camera_move_speed: 100.0,
             camera_zoom_speed: 0.1,
+            separation_weight: 50.0,
+            separation_radius: 1.5 * TILE_SIZE,
+            edge_scroll_margin: 20.0,
+            pan_acceleration: 800.0,
+            pan_damping: 8.0,
+            zoom_smoothing: 10.0,
+            focus_speed: 5.0,
+            min_scale: 0.1,
+            max_scale: 10.0,
+            key_up: vec![KeyCode::KeyW],
+            key_down: vec![KeyCode::KeyS],
+            key_left: vec![KeyCode::KeyA],
+            key_right: vec![KeyCode::KeyD],
+            key_fast: vec![KeyCode::ShiftLeft],
+            key_focus: vec![KeyCode::KeyF],
+        }
+    }
+}
+
+// Eased pan and zoom velocities so the camera coasts instead of snapping.
+#[derive(Resource, Default)]
+struct CameraMotion {
+    pan_velocity: Vec2,
+    zoom_velocity: f32,
+}
+
+// Saved camera viewpoints. Slots 0..9 map to Ctrl+1..9; `active` is the
+// bookmark the `C` key is currently cycled to, or None for the free view.
+#[derive(Resource)]
+struct CameraBookmarks {
+    slots: Vec<Option<(Vec3, f32)>>,
+    active: Option<usize>,
+}
+
+impl Default for CameraBookmarks {
+    fn default() -> Self {
+        Self {
+            slots: vec![None; 9],
+            active: None,
         }
     }
 }
@@ -202,77 +286,279 @@ fn log_mouse_position(
     debug_log.add(format!("              tle: {}", string_i(mouse_position.tile)));
 }
 
-fn select_pawn(
+// On left press, record the field anchor and clear the previous selection.
+fn begin_drag_select(
     mouse_button: Res<ButtonInput<MouseButton>>,
-    asset_server: Res<AssetServer>,
+    mouse_position: Res<MousePosition>,
     selecters: Query<Entity, With<Selecter>>,
-    things: Query<(&Thing, Entity), (With<Thing>, Without<Selecter>)>,
+    mut drag_select: ResMut<DragSelect>,
     mut commands: Commands,
 ) {
-    // if no click then return
     if !mouse_button.just_pressed(MouseButton::Left) { return };
+    let Some(field) = mouse_position.field else { return };
 
     // Remove all selecters
     selecters.iter().for_each(|e| commands.entity(e).despawn());
 
-    // get the hovered thing
-    let Some(hovered) = things.iter().filter(|(c, _)| c.hovered).next() else { return };
-    let hovered = hovered.1;
+    drag_select.anchor = Some(field);
+}
+
+// While the button is held, draw a translucent quad from the anchor to the cursor.
+fn update_drag_select(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    mouse_position: Res<MousePosition>,
+    drag_select: Res<DragSelect>,
+    mut boxes: Query<(Entity, &mut Sprite, &mut Transform), With<SelectionBox>>,
+    mut commands: Commands,
+) {
+    let (Some(anchor), Some(field)) = (drag_select.anchor, mouse_position.field) else { return };
+    if !mouse_button.pressed(MouseButton::Left) { return };
+
+    let center = (anchor + field) / 2.0;
+    let size = (field - anchor).abs();
+
+    if let Some((_, mut sprite, mut transform)) = boxes.iter_mut().next() {
+        sprite.custom_size = Some(size);
+        transform.translation = Vec3::new(center.x, center.y, 2.0);
+    } else {
+        let mut sprite = Sprite::from_color(Color::srgba(0.3, 0.6, 1.0, 0.25), size);
+        sprite.custom_size = Some(size);
+        commands.spawn((
+            sprite,
+            Transform::from_xyz(center.x, center.y, 2.0),
+            SelectionBox,
+        ));
+    }
+}
+
+// On release, select every Thing whose transform falls inside the rectangle.
+fn end_drag_select(
+    mouse_button: Res<ButtonInput<MouseButton>>,
+    mouse_position: Res<MousePosition>,
+    asset_server: Res<AssetServer>,
+    boxes: Query<Entity, With<SelectionBox>>,
+    things: Query<(&Thing, &Transform, Entity), (With<Thing>, Without<Selecter>)>,
+    mut drag_select: ResMut<DragSelect>,
+    mut commands: Commands,
+) {
+    if !mouse_button.just_released(MouseButton::Left) { return };
+    let Some(anchor) = drag_select.anchor.take() else { return };
+
+    // Tear down the rubber-band quad.
+    boxes.iter().for_each(|e| commands.entity(e).despawn());
+
+    let field = mouse_position.field.unwrap_or(anchor);
+    let min = anchor.min(field);
+    let max = anchor.max(field);
 
-    // create a selecter as a child of the hovered things
     let image_hundle = asset_server.load("embedded://frame.png");
-    let child = commands.spawn((
-        Sprite::from_image(image_hundle),
-        Transform::from_xyz(0.0, 0.0, 1.5),
-        Selecter,
-    )).id();
 
-    commands.entity(hovered).add_child(child);
+    for (thing, transform, entity) in things.iter() {
+        let pos = transform.translation.truncate();
+        // A click with no drag still selects whatever is hovered.
+        let inside = (min.x..=max.x).contains(&pos.x) && (min.y..=max.y).contains(&pos.y);
+        if !inside && !thing.hovered { continue };
+
+        let child = commands.spawn((
+            Sprite::from_image(image_hundle.clone()),
+            Transform::from_xyz(0.0, 0.0, 1.5),
+            Selecter,
+        )).id();
+
+        commands.entity(entity).add_child(child);
+    }
+}
+
+// A deterministic square-spiral offset so grouped pawns fan out around the
+// clicked tile instead of stacking on the same coordinate.
+fn formation_offset(index: usize) -> IVec2 {
+    let (mut x, mut y) = (0i32, 0i32);
+    let (mut dx, mut dy) = (0i32, -1i32);
+    for _ in 0..index {
+        if x == y || (x < 0 && x == -y) || (x > 0 && x == 1 - y) {
+            let t = dx;
+            dx = -dy;
+            dy = t;
+        }
+        x += dx;
+        y += dy;
+    }
+    IVec2::new(x, y)
+}
+
+// Round a field position to its grid tile.
+fn world_to_tile(pos: Vec2) -> IVec2 {
+    IVec2::new(
+        (pos.x / TILE_SIZE).round() as i32,
+        (pos.y / TILE_SIZE).round() as i32,
+    )
+}
+
+// A* over the TILE_RANGE x TILE_RANGE grid. Costs use fixed-point units
+// (10 per cardinal step, 14 per diagonal) so the open set can stay integer
+// keyed. Returns the tile waypoints from the first step through the goal, or
+// None when the goal is out of bounds, blocked, or unreachable.
+fn find_path(start: IVec2, goal: IVec2, blocked: &HashSet<IVec2>) -> Option<VecDeque<IVec2>> {
+    const HALF: i32 = TILE_RANGE / 2;
+    let in_bounds = |p: IVec2| (-HALF..HALF).contains(&p.x) && (-HALF..HALF).contains(&p.y);
+
+    if !in_bounds(goal) || blocked.contains(&goal) { return None };
+
+    // Octile distance in the same fixed-point units.
+    let heuristic = |p: IVec2| {
+        let dx = (p.x - goal.x).abs();
+        let dy = (p.y - goal.y).abs();
+        10 * (dx + dy) - 6 * dx.min(dy)
+    };
+
+    const NEIGHBORS: [(i32, i32); 8] = [
+        (1, 0), (-1, 0), (0, 1), (0, -1),
+        (1, 1), (1, -1), (-1, 1), (-1, -1),
+    ];
+
+    let mut open: BinaryHeap<Reverse<(i32, (i32, i32))>> = BinaryHeap::new();
+    let mut came_from: HashMap<IVec2, IVec2> = HashMap::new();
+    let mut g_score: HashMap<IVec2, i32> = HashMap::new();
+    let mut closed: HashSet<IVec2> = HashSet::new();
+
+    g_score.insert(start, 0);
+    open.push(Reverse((heuristic(start), (start.x, start.y))));
+
+    while let Some(Reverse((_, (cx, cy)))) = open.pop() {
+        let current = IVec2::new(cx, cy);
+
+        if current == goal {
+            let mut path = VecDeque::new();
+            let mut node = goal;
+            while node != start {
+                path.push_front(node);
+                node = came_from[&node];
+            }
+            return Some(path);
+        }
+
+        if !closed.insert(current) { continue };
+        let current_g = g_score[&current];
+
+        for (dx, dy) in NEIGHBORS {
+            let next = IVec2::new(cx + dx, cy + dy);
+            if !in_bounds(next) || blocked.contains(&next) || closed.contains(&next) { continue };
+
+            let tentative = current_g + if dx != 0 && dy != 0 { 14 } else { 10 };
+            if tentative < *g_score.get(&next).unwrap_or(&i32::MAX) {
+                came_from.insert(next, current);
+                g_score.insert(next, tentative);
+                open.push(Reverse((tentative + heuristic(next), (next.x, next.y))));
+            }
+        }
+    }
+
+    None
 }
 
 fn let_move_pawn(
     mouse_position: Res<MousePosition>,
     mouse_button: Res<ButtonInput<MouseButton>>,
     selecter: Query<Entity, With<Selecter>>,
-    pawns: Query<(&Children, &mut Pawn), With<Pawn>>,
+    things: Query<&Transform, With<Thing>>,
+    mut pawns: Query<(&Children, &Transform, &mut Pawn), With<Pawn>>,
+    mut debug_log: Query<&mut DebugLog>,
 ) {
     if !mouse_button.just_pressed(MouseButton::Right) { return };
-    let Some(mouse_position) = mouse_position.field_tile_rounded else { return };
-
-    for (children, mut pawn) in pawns {
-        for &child in children {
-            if let Ok(_) = selecter.get(child) {
-                pawn.state = PawnState::Move(mouse_position);
+    let Some(goal_tile) = mouse_position.tile else { return };
+
+    // Tiles occupied by any Thing are impassable.
+    let blocked: HashSet<IVec2> = things
+        .iter()
+        .map(|t| world_to_tile(t.translation.truncate()))
+        .collect();
+
+    // Issue a path to every selected pawn, spreading goals so they don't all
+    // converge on one tile.
+    let mut index = 0;
+    for (children, transform, mut pawn) in pawns.iter_mut() {
+        if !children.iter().any(|&child| selecter.get(child).is_ok()) { continue };
+
+        let start = world_to_tile(transform.translation.truncate());
+        let goal = goal_tile + formation_offset(index);
+        index += 1;
+
+        match find_path(start, goal, &blocked) {
+            Some(path) => pawn.state = PawnState::Move(path),
+            None => {
+                pawn.state = PawnState::Idle;
+                if let Ok(mut log) = debug_log.single_mut() {
+                    log.add(format!("No path from ({},{}) to ({},{})", start.x, start.y, goal.x, goal.y));
+                }
             }
         }
     }
 }
 
 fn move_pawn(
-    mut pawns: Query<(&mut Pawn, &mut Transform)>,
+    mut pawns: Query<(Entity, &mut Pawn, &mut Transform)>,
+    settings: Res<Settings>,
     time: Res<Time>,
 ) {
-    for (mut component, mut transform) in pawns.iter_mut() {
-        // if pawn's state is not Move then continue
-        let PawnState::Move(destination) = component.state else { continue };
+    // Rebuild a coarse spatial hash each frame so separation only tests the
+    // neighboring tile buckets instead of every other pawn. The scan reaches
+    // as many buckets as the radius spans so no in-range neighbor is missed.
+    let reach = (settings.separation_radius / TILE_SIZE).ceil() as i32;
+    let mut positions: HashMap<Entity, Vec2> = HashMap::new();
+    let mut buckets: HashMap<IVec2, Vec<Entity>> = HashMap::new();
+    for (entity, _, transform) in pawns.iter() {
+        let position = transform.translation.truncate();
+        positions.insert(entity, position);
+        buckets.entry(world_to_tile(position)).or_default().push(entity);
+    }
 
-        // straight angle to the destination
-        let theta = (transform.translation.y - destination.y).atan2(transform.translation.x - destination.x) + PI as f32;
-        let distance = transform.translation.distance(Vec3::new(destination.x, destination.y, 1.0));
+    for (entity, mut component, mut transform) in pawns.iter_mut() {
+        let mut finished = false;
+
+        if let PawnState::Move(path) = &mut component.state {
+            if let Some(&waypoint) = path.front() {
+                let target = waypoint.as_vec2() * TILE_SIZE;
+                let position = transform.translation.truncate();
+                let distance = position.distance(target);
+
+                if distance < 0.1 {
+                    // Snap to the waypoint and advance the queue.
+                    transform.translation.x = target.x;
+                    transform.translation.y = target.y;
+                    path.pop_front();
+                } else {
+                    // Repulsion from nearby pawns, weighted by 1 / distance.
+                    let mut separation = Vec2::ZERO;
+                    let tile = world_to_tile(position);
+                    for dx in -reach..=reach {
+                        for dy in -reach..=reach {
+                            let Some(bucket) = buckets.get(&(tile + IVec2::new(dx, dy))) else { continue };
+                            for &other in bucket {
+                                if other == entity { continue };
+                                let offset = position - positions[&other];
+                                let d = offset.length();
+                                if d > 0.0 && d < settings.separation_radius {
+                                    separation += offset / (d * d);
+                                }
+                            }
+                        }
+                    }
+
+                    // Blend "toward destination" with the separation force.
+                    let toward = (target - position) / distance;
+                    let direction = (toward + separation * settings.separation_weight).normalize_or_zero();
+                    let speed = distance.min(10.0) * 10.0;
+                    transform.translation.x += direction.x * speed * time.delta_secs();
+                    transform.translation.y += direction.y * speed * time.delta_secs();
+                }
+            }
 
-        if distance < 0.1 {
-            component.state = PawnState::Idle;
-            transform.translation = Vec3::new(destination.x, destination.y, 1.0);
-            return;
+            // Fall back to Idle once every waypoint has been consumed.
+            if path.is_empty() { finished = true };
         }
 
-        // The speed increase is capped at 10.0
-        let speed = distance.min(10.0) * 10.0;
-
-        // The intersection of the straight line to the destination and the unit circle with itself as the origin is the coordinate to move forward.
-        transform.translation.x += theta.cos() * speed * time.delta_secs();
-        transform.translation.y += theta.sin() * speed * time.delta_secs();
-    };
+        if finished { component.state = PawnState::Idle };
+    }
 }
 
 fn is_thing_hovered(
@@ -308,6 +594,10 @@ fn spawn_tile(
                 Sprite::from_image(image_hundle.clone()),
                 Transform::from_xyz(t(i), t(j), 0.0)
                     .with_scale(Vec3::splat(TILE_SIZE / 512.0)),
+                Tile {
+                    position: IVec2::new(i, j),
+                    key: DEFAULT_TILE_KEY.to_string(),
+                },
             ))
             .with_child((
                 Text2d::new(format!("({},{})", i, j)),
@@ -318,11 +608,148 @@ fn spawn_tile(
     }
 }
 
+// F5 serializes every placed Tile and Thing to MAP_PATH.
+fn save_map(
+    keys: Res<ButtonInput<KeyCode>>,
+    tiles: Query<&Tile>,
+    things: Query<(&Transform, Option<&Pawn>), With<Thing>>,
+    mut debug_log: Query<&mut DebugLog>,
+) {
+    if !keys.just_pressed(KeyCode::F5) { return };
+
+    const HALF: i32 = TILE_RANGE / 2;
+    let size = TILE_RANGE as usize;
+
+    // Resolve each tile's key into the key list and record its real index.
+    let mut tile_keys: Vec<String> = Vec::new();
+    let mut tile_values = vec![vec![0u8; size]; size];
+
+    for tile in tiles.iter() {
+        let x = (tile.position.x + HALF) as usize;
+        let y = (tile.position.y + HALF) as usize;
+        if x < size && y < size {
+            let index = tile_keys.iter().position(|k| k == &tile.key).unwrap_or_else(|| {
+                tile_keys.push(tile.key.clone());
+                tile_keys.len() - 1
+            });
+            tile_values[x][y] = index as u8;
+        }
+    }
+
+    if tile_keys.is_empty() {
+        tile_keys.push(DEFAULT_TILE_KEY.to_string());
+    }
+
+    // Record every Thing's tile, flagging the controllable pawns.
+    let things: Vec<ThingRecord> = things
+        .iter()
+        .map(|(transform, pawn)| {
+            let tile = world_to_tile(transform.translation.truncate());
+            ThingRecord {
+                position: [tile.x, tile.y],
+                pawn: pawn.is_some(),
+            }
+        })
+        .collect();
+
+    let map = MapInfo { tile_keys, tile_values, things };
+
+    let message = match toml::to_string(&map) {
+        Ok(contents) => match std::fs::write(MAP_PATH, contents) {
+            Ok(()) => format!("Saved map to {}", MAP_PATH),
+            Err(e) => format!("Failed to write {}: {}", MAP_PATH, e),
+        },
+        Err(e) => format!("Failed to serialize map: {}", e),
+    };
+
+    if let Ok(mut log) = debug_log.single_mut() {
+        log.add(message);
+    }
+}
+
+// F9 reloads MAP_PATH, despawning current tiles and things and spawning the
+// saved grid and pawns.
 fn load_map(
-    mut tiles: Query<Entity, With<Tile>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    tiles: Query<Entity, With<Tile>>,
+    things: Query<Entity, With<Thing>>,
+    mut debug_log: Query<&mut DebugLog>,
+    mut commands: Commands,
 ) {
+    if !keys.just_pressed(KeyCode::F9) { return };
+
+    let contents = match std::fs::read_to_string(MAP_PATH) {
+        Ok(contents) => contents,
+        Err(e) => {
+            if let Ok(mut log) = debug_log.single_mut() {
+                log.add(format!("Failed to read {}: {}", MAP_PATH, e));
+            }
+            return;
+        }
+    };
+
+    let map: MapInfo = match toml::from_str(&contents) {
+        Ok(map) => map,
+        Err(e) => {
+            if let Ok(mut log) = debug_log.single_mut() {
+                log.add(format!("Failed to parse {}: {}", MAP_PATH, e));
+            }
+            return;
+        }
+    };
+
+    // Clear the current grid and things before rebuilding them.
     for tile in tiles.iter() {
-        
+        commands.entity(tile).despawn();
+    }
+    for thing in things.iter() {
+        commands.entity(thing).despawn();
+    }
+
+    const HALF: i32 = TILE_RANGE / 2;
+    let t: fn(i32) -> f32 = |i| i as f32 * TILE_SIZE;
+
+    for (xi, column) in map.tile_values.iter().enumerate() {
+        for (yi, &key_index) in column.iter().enumerate() {
+            let Some(key) = map.tile_keys.get(key_index as usize) else { continue };
+
+            let x = xi as i32 - HALF;
+            let y = yi as i32 - HALF;
+            let image_hundle = asset_server.load(format!("embedded://{}.png", key));
+
+            commands.spawn((
+                Sprite::from_image(image_hundle),
+                Transform::from_xyz(t(x), t(y), 0.0)
+                    .with_scale(Vec3::splat(TILE_SIZE / 512.0)),
+                Tile {
+                    position: IVec2::new(x, y),
+                    key: key.clone(),
+                },
+            ));
+        }
+    }
+
+    // Respawn the recorded things; pawns get their Pawn component back.
+    let pawn_hundle = asset_server.load("embedded://pawn.png");
+    for record in &map.things {
+        let [x, y] = record.position;
+        let mut entity = commands.spawn((
+            Sprite::from_image(pawn_hundle.clone()),
+            Transform::from_xyz(t(x), t(y), 1.0)
+                .with_scale(Vec3::splat(TILE_SIZE / 512.0)),
+            Thing {
+                hovered: false,
+                _selected: false,
+            },
+        ));
+        if record.pawn {
+            entity.insert(Pawn::default());
+        }
+    }
+
+    if let Ok(mut log) = debug_log.single_mut() {
+        log.add(format!("Loaded map from {}", MAP_PATH));
     }
 }
 
@@ -345,37 +772,84 @@ fn move_camera(
     mouse_button: Res<ButtonInput<MouseButton>>,
     settings: Res<Settings>,
     time: Res<Time>,
+    window: Query<&Window>,
+    selected: Query<(&Children, &Transform), (With<Pawn>, Without<Camera2d>)>,
+    selecters: Query<(), With<Selecter>>,
+    bookmarks: Res<CameraBookmarks>,
+    mut motion: ResMut<CameraMotion>,
     mut camera: Query<&mut Transform, With<Camera2d>>,
 ) {
+    // An active bookmark drives the camera instead of the free WASD view.
+    if bookmarks.active.is_some() { return };
+
     let Some(mut camera) = camera.single_mut().ok() else { return };
-    let mut distance = Vec2::ZERO;
+    let held = |action: &[KeyCode]| action.iter().any(|k| keys.pressed(*k));
 
+    // Middle-drag keeps its direct 1:1 pan and cancels any coasting.
     if mouse_button.pressed(MouseButton::Middle) {
-        if let (
-            Some(field),
-            Some(field_before_middle)
-        ) = (
+        if let (Some(field), Some(field_before_middle)) = (
             mouse_position.field,
-            mouse_position.field_before_middle_pressed
+            mouse_position.field_before_middle_pressed,
         ) {
             camera.translation.x += field_before_middle.x - field.x;
             camera.translation.y += field_before_middle.y - field.y;
         }
-    } else {
-        if keys.pressed(KeyCode::KeyW) { distance.y += 1.0 }
-        if keys.pressed(KeyCode::KeyA) { distance.x -= 1.0 }
-        if keys.pressed(KeyCode::KeyS) { distance.y -= 1.0 }
-        if keys.pressed(KeyCode::KeyD) { distance.x += 1.0 }
-
-        let shift_multiplier = if keys.pressed(KeyCode::ShiftLeft) { 10.0 } else { 1.0 };
+        motion.pan_velocity = Vec2::ZERO;
+        return;
+    }
 
-        if distance.length_squared() > 0.0 {
-            distance = distance.normalize();
+    // Focus smoothly lerps to the centroid of the selected pawns.
+    if held(&settings.key_focus) {
+        let mut sum = Vec2::ZERO;
+        let mut count = 0;
+        for (children, transform) in &selected {
+            if children.iter().any(|&child| selecters.get(child).is_ok()) {
+                sum += transform.translation.truncate();
+                count += 1;
+            }
         }
+        if count > 0 {
+            let centroid = sum / count as f32;
+            let current = camera.translation.truncate();
+            let next = current.lerp(centroid, (settings.focus_speed * time.delta_secs()).min(1.0));
+            camera.translation.x = next.x;
+            camera.translation.y = next.y;
+            motion.pan_velocity = Vec2::ZERO;
+            return;
+        }
+    }
 
-        camera.translation.x += distance.x * settings.camera_move_speed * shift_multiplier * time.delta_secs();
-        camera.translation.y += distance.y * settings.camera_move_speed * shift_multiplier * time.delta_secs();
+    // Keyboard direction plus screen-edge scrolling.
+    let mut direction = Vec2::ZERO;
+    if held(&settings.key_up) { direction.y += 1.0 }
+    if held(&settings.key_down) { direction.y -= 1.0 }
+    if held(&settings.key_left) { direction.x -= 1.0 }
+    if held(&settings.key_right) { direction.x += 1.0 }
+
+    if let (Ok(window), Some(cursor)) = (window.single(), mouse_position.window) {
+        let margin = settings.edge_scroll_margin;
+        if cursor.x < margin { direction.x -= 1.0 }
+        if cursor.x > window.width() - margin { direction.x += 1.0 }
+        if cursor.y < margin { direction.y += 1.0 }
+        if cursor.y > window.height() - margin { direction.y -= 1.0 }
     }
+
+    let fast = if held(&settings.key_fast) { 10.0 } else { 1.0 };
+
+    // Ease the velocity in toward the input and damp it when released.
+    if direction.length_squared() > 0.0 {
+        direction = direction.normalize();
+        motion.pan_velocity += direction * settings.pan_acceleration * fast * time.delta_secs();
+    }
+    motion.pan_velocity *= 1.0 - (settings.pan_damping * time.delta_secs()).min(1.0);
+
+    let max_speed = settings.camera_move_speed * fast;
+    if motion.pan_velocity.length() > max_speed {
+        motion.pan_velocity = motion.pan_velocity.normalize() * max_speed;
+    }
+
+    camera.translation.x += motion.pan_velocity.x * time.delta_secs();
+    camera.translation.y += motion.pan_velocity.y * time.delta_secs();
 }
 
 fn log_camera_scale(
@@ -396,26 +870,94 @@ fn zoom_camera(
     keys: Res<ButtonInput<KeyCode>>,
     settings: Res<Settings>,
     time: Res<Time>,
+    bookmarks: Res<CameraBookmarks>,
+    mut motion: ResMut<CameraMotion>,
 ) {
+    // An active bookmark owns the zoom, just like it owns the pan.
+    if bookmarks.active.is_some() {
+        motion.zoom_velocity = 0.0;
+        return;
+    }
+
     let Some(mut camera) = camera.single_mut().ok() else { return };
-    let Some(ev) = event_read_scroll.read().next() else { return };
 
-    let shift_multiplier =
-        if keys.pressed(KeyCode::ShiftLeft) {
-            10.0
-        } else {
-            1.0
-        };
+    let fast = if settings.key_fast.iter().any(|k| keys.pressed(*k)) { 10.0 } else { 1.0 };
 
-    let change = ev.y * settings.camera_zoom_speed * shift_multiplier * 20.0 * time.delta_secs();
+    // Scroll feeds a velocity that eases out instead of snapping.
+    for ev in event_read_scroll.read() {
+        motion.zoom_velocity -= ev.y * settings.camera_zoom_speed * fast;
+    }
 
     if let Projection::Orthographic(ortho) = camera.as_mut() {
-        let post_scale = ortho.scale - change;
+        let change = motion.zoom_velocity * settings.zoom_smoothing * time.delta_secs();
+        ortho.scale = (ortho.scale + change).clamp(settings.min_scale, settings.max_scale);
+    };
+
+    motion.zoom_velocity *= 1.0 - (settings.zoom_smoothing * time.delta_secs()).min(1.0);
+}
 
-        if (0.1..10.0).contains(&post_scale) {
-            ortho.scale -= change;
+fn camera_bookmarks(
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    mut debug_log: Query<&mut DebugLog>,
+    mut camera: Query<(&mut Transform, &mut Projection), With<Camera2d>>,
+) {
+    let Some((mut transform, mut projection)) = camera.single_mut().ok() else { return };
+
+    // Ctrl+1..9 records the current view into a slot.
+    const DIGITS: [KeyCode; 9] = [
+        KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3,
+        KeyCode::Digit4, KeyCode::Digit5, KeyCode::Digit6,
+        KeyCode::Digit7, KeyCode::Digit8, KeyCode::Digit9,
+    ];
+    if keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight) {
+        for (i, digit) in DIGITS.iter().enumerate() {
+            if keys.just_pressed(*digit) {
+                let scale = if let Projection::Orthographic(o) = projection.as_ref() { o.scale } else { 1.0 };
+                bookmarks.slots[i] = Some((transform.translation, scale));
+                if let Ok(mut log) = debug_log.single_mut() {
+                    log.add(format!("Saved camera bookmark {}", i + 1));
+                }
+            }
         }
-    };
+    }
+
+    // C cycles through the saved slots, then wraps back to the free view.
+    if keys.just_pressed(KeyCode::KeyC) {
+        let saved: Vec<usize> = bookmarks.slots.iter()
+            .enumerate()
+            .filter(|(_, slot)| slot.is_some())
+            .map(|(i, _)| i)
+            .collect();
+
+        bookmarks.active = match bookmarks.active {
+            None => saved.first().copied(),
+            Some(current) => match saved.iter().position(|&i| i == current) {
+                Some(pos) if pos + 1 < saved.len() => Some(saved[pos + 1]),
+                _ => None,
+            },
+        };
+
+        if let Ok(mut log) = debug_log.single_mut() {
+            match bookmarks.active {
+                Some(i) => log.add(format!("Camera bookmark {}", i + 1)),
+                None => log.add("Camera free view".to_string()),
+            }
+        }
+    }
+
+    // Smoothly approach the active bookmark.
+    if let Some(i) = bookmarks.active {
+        if let Some((target_translation, target_scale)) = bookmarks.slots[i] {
+            let t = (settings.focus_speed * time.delta_secs()).min(1.0);
+            transform.translation = transform.translation.lerp(target_translation, t);
+            if let Projection::Orthographic(o) = projection.as_mut() {
+                o.scale += (target_scale - o.scale) * t;
+            }
+        }
+    }
 }
 
 fn output_log(
@@ -471,6 +1013,9 @@ fn main() {
             DefaultPlugins,
         ))
         .insert_resource(Settings::default())
+        .insert_resource(DragSelect::default())
+        .insert_resource(CameraMotion::default())
+        .insert_resource(CameraBookmarks::default())
         .insert_resource(MousePosition{
             window: None,
             field: None,
@@ -485,12 +1030,17 @@ fn main() {
         ))
         .add_systems(Update, (
             send_resouce_mouse_position,
-            select_pawn,
+            begin_drag_select,
+            update_drag_select,
+            end_drag_select,
             let_move_pawn,
             move_pawn,
             is_thing_hovered,
             move_camera,
             zoom_camera,
+            save_map,
+            load_map,
+            camera_bookmarks,
             close_on_q,
         ))
         .add_systems(Update, (